@@ -0,0 +1,31 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Draw a resampled set of configuration indices for a single bootstrap
+/// replicate, blocking configurations into `binwidth`-sized chunks.
+///
+/// When `seed` is given, the RNG is seeded from it so that the same seed
+/// always reproduces the same sample (callers derive a per-replicate seed
+/// with `replicate_seed`); otherwise a fresh entropy-seeded RNG is used.
+pub fn get_samples(nconfs: usize, binwidth: usize, seed: Option<u64>) -> Vec<usize> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let nbins = nconfs / binwidth;
+    let mut samples = Vec::with_capacity(nbins * binwidth);
+    for _ in 0..nbins {
+        let bin_start = rng.gen_range(0..nbins) * binwidth;
+        for offset in 0..binwidth {
+            samples.push(bin_start + offset);
+        }
+    }
+    samples
+}
+
+/// Derive a per-replicate seed from a run seed and the replicate index, so
+/// that every replicate in a parallel bootstrap gets its own deterministic
+/// stream rather than all sharing one RNG state.
+pub fn replicate_seed(seed: u64, replicate: u32) -> u64 {
+    seed ^ (replicate as u64)
+}
@@ -0,0 +1,153 @@
+use crate::observables::Measurement;
+use crate::statistics::mean;
+use crate::wilsonflow::{Series, WilsonFlowData};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+
+/// Wraps `path` in a gzip decoder when it ends in `.gz`.
+fn open_maybe_gzipped(path: &str) -> Box<dyn BufRead> {
+    let file = File::open(path).unwrap();
+    if path.ends_with(".gz") {
+        Box::new(BufReader::new(GzDecoder::new(file)))
+    } else {
+        Box::new(BufReader::new(file))
+    }
+}
+
+/// Writes to `output`, or stdout when `None`; a `.gz` path gzips the stream.
+pub fn writer_for(output: &Option<String>) -> Box<dyn Write> {
+    match output {
+        None => Box::new(std::io::stdout()),
+        Some(path) if path.ends_with(".gz") => {
+            Box::new(GzEncoder::new(File::create(path).unwrap(), Compression::default()))
+        }
+        Some(path) => Box::new(File::create(path).unwrap()),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CorrelatorRow {
+    channel: String,
+    config: usize,
+    tau: usize,
+    value: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct WilsonFlowRow {
+    config: usize,
+    t: f64,
+    t2_esym: f64,
+    tc: f64,
+}
+
+/// A folded correlator channel, `each_len` timeslices wide.
+pub struct Channel {
+    pub nconfs: usize,
+    pub each_len: usize,
+    data: Vec<Vec<f64>>,
+}
+
+impl Channel {
+    pub fn thermalise(mut self, thermalisation: usize) -> Self {
+        self.data.drain(0..thermalisation.min(self.data.len()));
+        self.nconfs = self.data.len();
+        self
+    }
+
+    pub fn get_subsample_mean_stderr(&self, binwidth: usize, seed: Option<u64>) -> Measurement {
+        self.get_subsample_mean_stderr_from_samples(crate::bootstrap::get_samples(
+            self.nconfs,
+            binwidth,
+            seed,
+        ))
+    }
+
+    pub fn get_subsample_mean_stderr_from_samples(&self, samples: Vec<usize>) -> Measurement {
+        let mut values = Vec::with_capacity(self.each_len);
+        for t in 0..self.each_len {
+            let column: Vec<f64> = samples.iter().map(|&i| self.data[i][t]).collect();
+            values.push(mean(&column));
+        }
+        Measurement { values }
+    }
+}
+
+/// Load a single channel's folded correlator data from `filename`.
+pub fn load_channel_from_file_folded(filename: &str, channel: &str) -> Channel {
+    let mut rdr = csv::ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .from_reader(open_maybe_gzipped(filename));
+    let mut by_config: Vec<Vec<f64>> = vec![];
+    for result in rdr.deserialize() {
+        let record: CorrelatorRow = result.unwrap();
+        if record.channel != channel {
+            continue;
+        }
+        if by_config.len() <= record.config {
+            by_config.resize(record.config + 1, vec![]);
+        }
+        if by_config[record.config].len() <= record.tau {
+            by_config[record.config].resize(record.tau + 1, 0.0);
+        }
+        by_config[record.config][record.tau] = record.value;
+    }
+    let each_len = by_config[0].len();
+    Channel {
+        nconfs: by_config.len(),
+        each_len,
+        data: by_config,
+    }
+}
+
+/// Read the lattice's global time extent from `filename`'s `# T=<value>` header.
+pub fn load_global_t_from_file(filename: &str) -> usize {
+    let rdr = open_maybe_gzipped(filename);
+    for line in rdr.lines() {
+        let line = line.unwrap();
+        if let Some(t) = line.strip_prefix("# T=") {
+            return t.trim().parse().unwrap();
+        }
+    }
+    panic!("no `# T=` header found in {filename}");
+}
+
+/// Load a Wilson-flow history (t²⟨E_sym⟩ and topological charge) from `filename`.
+pub fn load_wf_observables_from_file(filename: &str) -> WilsonFlowData {
+    let mut rdr = csv::ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .from_reader(open_maybe_gzipped(filename));
+    let mut t = vec![];
+    let mut t2_esym_by_config: Vec<Vec<f64>> = vec![];
+    let mut tc_by_config: Vec<Vec<f64>> = vec![];
+    for result in rdr.deserialize() {
+        let record: WilsonFlowRow = result.unwrap();
+        if by_config_needs_row(&t, record.t) {
+            t.push(record.t);
+        }
+        let tau = t.iter().position(|&x| x == record.t).unwrap();
+        for by_config in [&mut t2_esym_by_config, &mut tc_by_config] {
+            if by_config.len() <= record.config {
+                by_config.resize(record.config + 1, vec![]);
+            }
+            if by_config[record.config].len() <= tau {
+                by_config[record.config].resize(tau + 1, 0.0);
+            }
+        }
+        t2_esym_by_config[record.config][tau] = record.t2_esym;
+        tc_by_config[record.config][tau] = record.tc;
+    }
+    WilsonFlowData {
+        t,
+        t2_esym: Series::new(t2_esym_by_config),
+        tc: Series::new(tc_by_config),
+    }
+}
+
+fn by_config_needs_row(t: &[f64], value: f64) -> bool {
+    !t.contains(&value)
+}
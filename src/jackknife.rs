@@ -0,0 +1,43 @@
+use crate::statistics::mean;
+
+/// Generate delete-`d` blocked jackknife subsample index sets: splits
+/// `nconfs` configurations into blocks of `d` and returns one index vector
+/// per block with that block's configurations removed.
+pub fn get_jackknife_subsamples(nconfs: usize, d: usize) -> Vec<Vec<usize>> {
+    let nblocks = nconfs / d;
+    (0..nblocks)
+        .map(|block| {
+            let start = block * d;
+            let end = start + d;
+            (0..nconfs).filter(|&i| i < start || i >= end).collect()
+        })
+        .collect()
+}
+
+/// Jackknife error estimate from the set of leave-block-out estimates:
+/// sigma^2 = (N_J - 1)/N_J * sum((theta_(i) - theta_bar)^2).
+pub fn jackknife_error(estimates: &[f64]) -> f64 {
+    let n_j = estimates.len() as f64;
+    let theta_bar = mean(estimates);
+    let sum_sq: f64 = estimates.iter().map(|&theta_i| (theta_i - theta_bar).powi(2)).sum();
+    ((n_j - 1.0) / n_j * sum_sq).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subsamples_drop_one_block_each() {
+        let subsamples = get_jackknife_subsamples(6, 2);
+        assert_eq!(subsamples.len(), 3);
+        assert_eq!(subsamples[0], vec![2, 3, 4, 5]);
+        assert_eq!(subsamples[1], vec![0, 1, 4, 5]);
+        assert_eq!(subsamples[2], vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn error_of_constant_estimates_is_zero() {
+        assert_eq!(jackknife_error(&[1.0, 1.0, 1.0]), 0.0);
+    }
+}
@@ -0,0 +1,12 @@
+mod bootstrap;
+mod io;
+mod jackknife;
+mod observables;
+mod parser;
+mod spectroscopy;
+mod statistics;
+mod wilsonflow;
+
+fn main() {
+    parser::parser();
+}
@@ -0,0 +1,5 @@
+/// A vector of values (one per timeslice or flow-time point), as produced by
+/// a resampling pass over a `Channel` or Wilson-flow series.
+pub struct Measurement {
+    pub values: Vec<f64>,
+}
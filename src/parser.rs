@@ -1,10 +1,14 @@
-use crate::bootstrap::get_samples;
+use crate::bootstrap::{get_samples, replicate_seed};
 use crate::io::{
     load_channel_from_file_folded, load_global_t_from_file, load_wf_observables_from_file,
+    writer_for,
 };
+use crate::jackknife::{get_jackknife_subsamples, jackknife_error};
 use crate::observables::Measurement;
 use crate::spectroscopy::effective_mass;
-use crate::statistics::{bin, mean, standard_deviation, standard_error, weighted_mean};
+use crate::statistics::{
+    bin, mean, normal_cdf, normal_cdf_inv, standard_deviation, standard_error, weighted_mean,
+};
 use crate::wilsonflow::{calculate_w, calculate_w0, WilsonFlowObservables};
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::generate;
@@ -44,6 +48,16 @@ enum Command {
         #[clap(flatten)]
         args: BootstrapFitsArgs,
     },
+    /// Delete-d blocked jackknife cross-check of `BootstrapFitsWithWF`
+    JackknifeFitsWithWF {
+        #[clap(flatten)]
+        args: JackknifeFitsWithWFArgs,
+    },
+    /// Delete-d blocked jackknife cross-check of `BootstrapFits`
+    JackknifeFits {
+        #[clap(flatten)]
+        args: JackknifeFitsArgs,
+    },
     BootstrapFitsRatio {
         #[clap(flatten)]
         args: BootstrapFitsRatioArgs,
@@ -60,6 +74,12 @@ enum Command {
         #[clap(flatten)]
         args: BootstrapErrorArgs,
     },
+    /// Compute a BCa confidence interval from a `BootstrapSample` CSV and the
+    /// original point estimate
+    BootstrapCI {
+        #[clap(flatten)]
+        args: BootstrapCIArgs,
+    },
     GenerateCompletions {},
 }
 
@@ -86,6 +106,25 @@ struct BinBootstrapArgs {
     n_boot: u32,
     #[arg(short, long, value_name = "BIN_WIDTH", default_value_t = 1)]
     binwidth: usize,
+    /// Collapse the bootstrap distribution into a mean/std/16-84 percentile row
+    /// instead of emitting every replicate
+    #[arg(long)]
+    summary: bool,
+    /// Seed the per-replicate RNGs for reproducible resampling; omit for
+    /// entropy-seeded (non-deterministic) replicates
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+}
+
+#[derive(Parser, Debug)]
+struct EffectiveMassBootstrapArgs {
+    #[arg(short, long, value_name = "BOOTSTRAP_SAMPLES", default_value_t = 1000)]
+    n_boot: u32,
+    #[arg(short, long, value_name = "BIN_WIDTH", default_value_t = 1)]
+    binwidth: usize,
+    /// Fix the resampling RNG for a reproducible effective-mass table
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
 }
 
 #[derive(Parser, Debug)]
@@ -93,7 +132,7 @@ struct ComputeEffectiveMassArgs {
     #[clap(flatten)]
     hmc: HMCArgs,
     #[clap(flatten)]
-    boot: BinBootstrapArgs,
+    boot: EffectiveMassBootstrapArgs,
     #[arg(short, long, value_name = "CHANNEL")]
     channel: String,
     #[arg(short, long, value_name = "SOLVER_PRECISION", default_value_t = 1e-15)]
@@ -102,6 +141,9 @@ struct ComputeEffectiveMassArgs {
     effective_mass_t_max: usize,
     #[arg(long, value_name = "EFFECTIVE_MASS_T_MIN")]
     effective_mass_t_min: usize,
+    /// Write the effective-mass table here instead of stdout; `.gz` gzips it
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -132,6 +174,9 @@ struct BootstrapFitsWithWFArgs {
     effective_mass_t_max: usize,
     #[arg(long, value_name = "EFFECTIVE_MASS_T_MIN")]
     effective_mass_t_min: usize,
+    /// Write the bootstrap fit replicates here instead of stdout; `.gz` gzips them
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<String>,
 }
 #[derive(Parser, Debug)]
 struct CalculateW0Args {
@@ -139,6 +184,56 @@ struct CalculateW0Args {
     boot: BinBootstrapArgs,
     #[clap(flatten)]
     wf: WFArgs,
+    /// Write the w0 bootstrap replicates here instead of stdout; `.gz` gzips them
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct BinJackknifeArgs {
+    /// Block size for the delete-d jackknife
+    #[arg(short = 'd', long, value_name = "BLOCK_SIZE", default_value_t = 1)]
+    d: usize,
+}
+
+#[derive(Parser, Debug)]
+struct JackknifeFitsWithWFArgs {
+    #[clap(flatten)]
+    hmc: HMCArgs,
+    #[clap(flatten)]
+    wf: WFArgs,
+    #[clap(flatten)]
+    jack: BinJackknifeArgs,
+    #[arg(short, long, value_name = "CHANNEL")]
+    channel: String,
+    #[arg(short, long, value_name = "SOLVER_PRECISION", default_value_t = 1e-15)]
+    solver_precision: f64,
+    #[arg(long, value_name = "EFFECTIVE_MASS_T_MAX")]
+    effective_mass_t_max: usize,
+    #[arg(long, value_name = "EFFECTIVE_MASS_T_MIN")]
+    effective_mass_t_min: usize,
+    /// Write to this path instead of stdout; a `.gz` suffix emits gzip
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct JackknifeFitsArgs {
+    #[clap(flatten)]
+    hmc: HMCArgs,
+    #[clap(flatten)]
+    jack: BinJackknifeArgs,
+    #[arg(short, long, value_name = "CHANNEL")]
+    channel: String,
+    #[arg(short, long, value_name = "SOLVER_PRECISION", default_value_t = 1e-15)]
+    solver_precision: f64,
+    #[arg(long, value_name = "EFFECTIVE_MASS_T_MAX")]
+    effective_mass_t_max: usize,
+    #[arg(long, value_name = "EFFECTIVE_MASS_T_MIN")]
+    effective_mass_t_min: usize,
+    /// Write the jackknife fit results here instead of stdout; `.gz` gzips them
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -155,6 +250,9 @@ struct BootstrapFitsArgs {
     effective_mass_t_max: usize,
     #[arg(long, value_name = "EFFECTIVE_MASS_T_MIN")]
     effective_mass_t_min: usize,
+    /// Write the bootstrap fit result here instead of stdout; `.gz` gzips it
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -177,6 +275,9 @@ struct BootstrapFitsRatioArgs {
     denominator_effective_mass_t_max: usize,
     #[arg(long, value_name = "DENOMINATOR_EFFECTIVE_MASS_T_MIN")]
     denominator_effective_mass_t_min: usize,
+    /// Write the ratio fit result here instead of stdout; `.gz` gzips it
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -184,6 +285,18 @@ struct BootstrapErrorArgs {
     csv_filename: String,
     #[arg(short, long, value_name = "BOOTSTRAP_SAMPLES", default_value_t = 1000)]
     n_boot: u32,
+    /// Reseed each replicate's resample deterministically; left unset, runs vary
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+}
+
+#[derive(Parser, Debug)]
+struct BootstrapCIArgs {
+    csv_filename: String,
+    #[arg(long, value_name = "POINT_ESTIMATE")]
+    point_estimate: f64,
+    #[arg(short, long, value_name = "CONFIDENCE_LEVEL", default_value_t = 0.68)]
+    confidence: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -209,6 +322,88 @@ struct BootstrapSample {
     #[serde(rename = "Sample")]
     sample: f64,
 }
+#[derive(Debug, Serialize)]
+struct BootstrapSummary {
+    #[serde(rename = "Mean")]
+    mean: f64,
+    #[serde(rename = "Std Dev")]
+    std: f64,
+    #[serde(rename = "16th Percentile")]
+    p16: f64,
+    #[serde(rename = "84th Percentile")]
+    p84: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct JackknifeResult {
+    #[serde(rename = "Mean")]
+    mean: f64,
+    #[serde(rename = "Jackknife Error")]
+    error: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct BCaInterval {
+    #[serde(rename = "Lower")]
+    lower: f64,
+    #[serde(rename = "Upper")]
+    upper: f64,
+}
+
+/// Percentile of an already-sorted slice: the element at the nearest rank,
+/// with no interpolation between neighbours
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let idx = ((sorted.len() - 1) as f64 * q).round() as usize;
+    sorted[idx]
+}
+
+/// BCa-adjusted percentile for a standard-normal quantile `z`, given the bias
+/// correction `z0` and acceleration `a`.
+fn bca_adjust(z0: f64, a: f64, z: f64) -> f64 {
+    normal_cdf(z0 + (z0 + z) / (1.0 - a * (z0 + z)))
+}
+
+/// Lower/upper percentiles of the BCa interval at confidence level `1 -
+/// alpha`, given the bias correction `z0` and acceleration `a`. Falls back to
+/// the plain (non-bias-corrected) percentile interval when `a` shows no
+/// detectable skew, or when `z0` is NaN (all or none of the replicates fell
+/// below the point estimate, which `normal_cdf_inv` can't bias-correct).
+fn bca_interval(z0: f64, a: f64, alpha: f64) -> (f64, f64) {
+    let (alpha1, alpha2) = if a == 0.0 || z0.is_nan() {
+        (alpha / 2.0, 1.0 - alpha / 2.0)
+    } else {
+        let z_lo = normal_cdf_inv(alpha / 2.0);
+        let z_hi = normal_cdf_inv(1.0 - alpha / 2.0);
+        (bca_adjust(z0, a, z_lo), bca_adjust(z0, a, z_hi))
+    };
+    (alpha1.clamp(0.0, 1.0), alpha2.clamp(0.0, 1.0))
+}
+
+/// Emit either the full set of bootstrap replicates or, when `summary` is set,
+/// a single collapsed row with the mean, standard deviation and 16th/84th
+/// percentile interval over the distribution
+fn write_bootstrap_output(results_g: Vec<f64>, summary: bool, output: &Option<String>) {
+    if summary && results_g.is_empty() {
+        panic!("no bootstrap replicates to summarize (did every root-find fail?)");
+    }
+    let mut wtr = csv::Writer::from_writer(writer_for(output));
+    if summary {
+        let mut sorted = results_g.clone();
+        sorted.sort_by(f64::total_cmp);
+        wtr.serialize(BootstrapSummary {
+            mean: mean(&results_g),
+            std: standard_deviation(&results_g, true),
+            p16: percentile(&sorted, 0.16),
+            p84: percentile(&sorted, 0.84),
+        })
+        .unwrap();
+    } else {
+        for sample in results_g {
+            wtr.serialize(BootstrapSample { sample }).unwrap();
+        }
+    }
+    wtr.flush().unwrap();
+}
 
 fn fit_effective_mass_command(args: FitEffectiveMassArgs) {
     let mut tau = vec![];
@@ -245,11 +440,11 @@ fn compute_effective_mass_command(args: ComputeEffectiveMassArgs) {
     for tau in 1..=args.effective_mass_t_max {
         let results: Vec<Result<f64, roots::SearchError>> = (0..args.boot.n_boot)
             .into_par_iter()
-            .map(|_| {
-                let Measurement {
-                    values: mu,
-                    errors: _,
-                } = channel.get_subsample_mean_stderr(args.boot.binwidth);
+            .map(|i| {
+                let Measurement { values: mu } = channel.get_subsample_mean_stderr(
+                    args.boot.binwidth,
+                    args.boot.seed.map(|seed| replicate_seed(seed, i)),
+                );
                 effective_mass(&mu, global_t, tau, args.solver_precision)
             })
             .collect();
@@ -265,7 +460,7 @@ fn compute_effective_mass_command(args: ComputeEffectiveMassArgs) {
         effmass_mean.push(mean(&effmass_inner));
         effmass_error.push(standard_deviation(&effmass_inner, true));
     }
-    let mut wtr = csv::Writer::from_writer(stdout());
+    let mut wtr = csv::Writer::from_writer(writer_for(&args.output));
     for tau in args.effective_mass_t_min..=args.effective_mass_t_max {
         wtr.serialize(EffectiveMassRow {
             tau,
@@ -288,8 +483,12 @@ fn bootstrap_fits_with_wf_command(args: BootstrapFitsWithWFArgs) {
     let mut results_g = vec![];
     let results = (0..args.boot.n_boot)
         .into_par_iter()
-        .map(|_| {
-            let samples = get_samples(channel.nconfs, args.boot.binwidth);
+        .map(|i| {
+            let samples = get_samples(
+                channel.nconfs,
+                args.boot.binwidth,
+                args.boot.seed.map(|seed| replicate_seed(seed, i)),
+            );
             let w0 = calculate_w0(
                 calculate_w(
                     &wf.get_subsample_mean_stderr_from_samples(
@@ -321,10 +520,52 @@ fn bootstrap_fits_with_wf_command(args: BootstrapFitsWithWFArgs) {
             Some(val) => results_g.push(val),
         };
     }
-    let mut wtr = csv::Writer::from_writer(stdout());
-    for sample in results_g {
-        wtr.serialize(BootstrapSample { sample }).unwrap();
-    }
+    write_bootstrap_output(results_g, args.boot.summary, &args.output);
+}
+fn jackknife_fits_with_wf_command(args: JackknifeFitsWithWFArgs) {
+    let channel = load_channel_from_file_folded(&args.hmc.filename, &args.channel)
+        .thermalise(args.hmc.thermalisation);
+    let wf =
+        load_wf_observables_from_file(&args.wf.wf_filename).thermalise(args.wf.wf_thermalisation);
+    assert_eq!(channel.nconfs, wf.tc.nconfs);
+    let global_t = load_global_t_from_file(&args.hmc.filename);
+    let estimates = get_jackknife_subsamples(channel.nconfs, args.jack.d)
+        .into_par_iter()
+        .map(|samples| {
+            let w0 = calculate_w0(
+                calculate_w(
+                    &wf.get_subsample_mean_stderr_from_samples(
+                        samples.clone(),
+                        WilsonFlowObservables::T2Esym,
+                    )
+                    .values,
+                    &wf.t,
+                ),
+                args.wf.w_ref,
+            );
+            let mut masses = vec![];
+            let mu = channel
+                .get_subsample_mean_stderr_from_samples(samples)
+                .values;
+            for tau in args.effective_mass_t_min..(args.effective_mass_t_max + 1) {
+                let mass = effective_mass(&mu, global_t, tau, args.solver_precision);
+                match mass {
+                    Err(_) => return None,
+                    Ok(val) => masses.push(val),
+                };
+            }
+            Some(mean(&masses) * w0)
+        })
+        .collect::<Vec<Option<f64>>>()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<f64>>();
+    let mut wtr = csv::Writer::from_writer(writer_for(&args.output));
+    wtr.serialize(JackknifeResult {
+        mean: mean(&estimates),
+        error: jackknife_error(&estimates),
+    })
+    .unwrap();
     wtr.flush().unwrap();
 }
 fn bootstrap_fits_command(args: BootstrapFitsArgs) {
@@ -334,8 +575,12 @@ fn bootstrap_fits_command(args: BootstrapFitsArgs) {
     let mut results_g = vec![];
     let results = (0..args.boot.n_boot)
         .into_par_iter()
-        .map(|_| {
-            let samples = get_samples(channel.nconfs, args.boot.binwidth);
+        .map(|i| {
+            let samples = get_samples(
+                channel.nconfs,
+                args.boot.binwidth,
+                args.boot.seed.map(|seed| replicate_seed(seed, i)),
+            );
             let mut masses = vec![];
             let mu = channel
                 .get_subsample_mean_stderr_from_samples(samples)
@@ -356,10 +601,38 @@ fn bootstrap_fits_command(args: BootstrapFitsArgs) {
             Some(val) => results_g.push(val),
         };
     }
-    let mut wtr = csv::Writer::from_writer(stdout());
-    for sample in results_g {
-        wtr.serialize(BootstrapSample { sample }).unwrap();
-    }
+    write_bootstrap_output(results_g, args.boot.summary, &args.output);
+}
+fn jackknife_fits_command(args: JackknifeFitsArgs) {
+    let channel = load_channel_from_file_folded(&args.hmc.filename, &args.channel)
+        .thermalise(args.hmc.thermalisation);
+    let global_t = load_global_t_from_file(&args.hmc.filename);
+    let estimates = get_jackknife_subsamples(channel.nconfs, args.jack.d)
+        .into_par_iter()
+        .map(|samples| {
+            let mut masses = vec![];
+            let mu = channel
+                .get_subsample_mean_stderr_from_samples(samples)
+                .values;
+            for tau in args.effective_mass_t_min..(args.effective_mass_t_max + 1) {
+                let mass = effective_mass(&mu, global_t, tau, args.solver_precision);
+                match mass {
+                    Err(_) => return None,
+                    Ok(val) => masses.push(val),
+                };
+            }
+            Some(mean(&masses))
+        })
+        .collect::<Vec<Option<f64>>>()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<f64>>();
+    let mut wtr = csv::Writer::from_writer(writer_for(&args.output));
+    wtr.serialize(JackknifeResult {
+        mean: mean(&estimates),
+        error: jackknife_error(&estimates),
+    })
+    .unwrap();
     wtr.flush().unwrap();
 }
 fn bootstrap_fits_ratio_command(args: BootstrapFitsRatioArgs) {
@@ -373,8 +646,12 @@ fn bootstrap_fits_ratio_command(args: BootstrapFitsRatioArgs) {
     let mut results_g = vec![];
     let results = (0..args.boot.n_boot)
         .into_par_iter()
-        .map(|_| {
-            let samples = get_samples(numerator_channel.nconfs, args.boot.binwidth);
+        .map(|i| {
+            let samples = get_samples(
+                numerator_channel.nconfs,
+                args.boot.binwidth,
+                args.boot.seed.map(|seed| replicate_seed(seed, i)),
+            );
 
             let mut num_masses = vec![];
             let num_mu = numerator_channel
@@ -412,19 +689,19 @@ fn bootstrap_fits_ratio_command(args: BootstrapFitsRatioArgs) {
             Some(val) => results_g.push(val),
         };
     }
-    let mut wtr = csv::Writer::from_writer(stdout());
-    for sample in results_g {
-        wtr.serialize(BootstrapSample { sample }).unwrap();
-    }
-    wtr.flush().unwrap();
+    write_bootstrap_output(results_g, args.boot.summary, &args.output);
 }
 fn calculate_w0_command(args: CalculateW0Args) {
     let wf =
         load_wf_observables_from_file(&args.wf.wf_filename).thermalise(args.wf.wf_thermalisation);
     let results = (0..args.boot.n_boot)
         .into_par_iter()
-        .map(|_| {
-            let samples = get_samples(wf.t2_esym.nconfs, args.boot.binwidth);
+        .map(|i| {
+            let samples = get_samples(
+                wf.t2_esym.nconfs,
+                args.boot.binwidth,
+                args.boot.seed.map(|seed| replicate_seed(seed, i)),
+            );
             calculate_w0(
                 calculate_w(
                     &wf.get_subsample_mean_stderr_from_samples(
@@ -438,11 +715,7 @@ fn calculate_w0_command(args: CalculateW0Args) {
             )
         })
         .collect::<Vec<f64>>();
-    let mut wtr = csv::Writer::from_writer(stdout());
-    for sample in results {
-        wtr.serialize(BootstrapSample { sample }).unwrap();
-    }
-    wtr.flush().unwrap();
+    write_bootstrap_output(results, args.boot.summary, &args.output);
 }
 
 fn histogram_command(args: HistogramArgs) {
@@ -470,9 +743,10 @@ fn bootstrap_error_command(args: BootstrapErrorArgs) {
     }
     let results = (0..args.n_boot)
         .into_par_iter()
-        .map(|_| {
+        .map(|i| {
             let mut tmp = vec![];
-            for index in get_samples(sample.len(), 1) {
+            for index in get_samples(sample.len(), 1, args.seed.map(|seed| replicate_seed(seed, i)))
+            {
                 tmp.push(sample[index]);
             }
             standard_error(&tmp)
@@ -485,6 +759,63 @@ fn bootstrap_error_command(args: BootstrapErrorArgs) {
     wtr.flush().unwrap();
 }
 
+fn bootstrap_ci_command(args: BootstrapCIArgs) {
+    let mut sample: Vec<f64> = vec![];
+    let mut rdr = csv::Reader::from_reader(File::open(&args.csv_filename).unwrap());
+    for result in rdr.deserialize() {
+        let record: BootstrapSample = result.unwrap();
+        sample.push(record.sample);
+    }
+    if sample.is_empty() {
+        panic!("{} contains no bootstrap samples", args.csv_filename);
+    }
+    let theta_hat = args.point_estimate;
+    let n = sample.len();
+
+    let below = sample.iter().filter(|&&v| v < theta_hat).count();
+    let z0 = normal_cdf_inv(below as f64 / n as f64);
+
+    let jackknife_means: Vec<f64> = (0..n)
+        .map(|i| {
+            let rest: Vec<f64> = sample
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &v)| v)
+                .collect();
+            mean(&rest)
+        })
+        .collect();
+    let theta_bar = mean(&jackknife_means);
+    let numerator: f64 = jackknife_means
+        .iter()
+        .map(|&theta_i| (theta_bar - theta_i).powi(3))
+        .sum();
+    let denominator = jackknife_means
+        .iter()
+        .map(|&theta_i| (theta_bar - theta_i).powi(2))
+        .sum::<f64>()
+        .powf(1.5);
+    let a = if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / (6.0 * denominator)
+    };
+
+    let alpha = 1.0 - args.confidence;
+    let (alpha1, alpha2) = bca_interval(z0, a, alpha);
+
+    let mut sorted = sample;
+    sorted.sort_by(f64::total_cmp);
+    let mut wtr = csv::Writer::from_writer(stdout());
+    wtr.serialize(BCaInterval {
+        lower: percentile(&sorted, alpha1),
+        upper: percentile(&sorted, alpha2),
+    })
+    .unwrap();
+    wtr.flush().unwrap();
+}
+
 pub fn parser() {
     let app = App::parse();
     match app.command {
@@ -492,12 +823,39 @@ pub fn parser() {
         Command::FitEffectiveMass { args } => fit_effective_mass_command(args),
         Command::BootstrapFitsWithWF { args } => bootstrap_fits_with_wf_command(args),
         Command::BootstrapFits { args } => bootstrap_fits_command(args),
+        Command::JackknifeFitsWithWF { args } => jackknife_fits_with_wf_command(args),
+        Command::JackknifeFits { args } => jackknife_fits_command(args),
         Command::BootstrapFitsRatio { args } => bootstrap_fits_ratio_command(args),
         Command::CalculateW0 { args } => calculate_w0_command(args),
         Command::Histogram { args } => histogram_command(args),
         Command::BootstrapError { args } => bootstrap_error_command(args),
+        Command::BootstrapCI { args } => bootstrap_ci_command(args),
         Command::GenerateCompletions {} => {
             generate(Nushell, &mut App::command(), "reshotka", &mut stdout())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_picks_nearest_rank() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 1.0), 5.0);
+        assert_eq!(percentile(&sorted, 0.5), 3.0);
+    }
+
+    #[test]
+    fn bca_adjust_with_no_bias_or_skew_is_identity() {
+        assert!((bca_adjust(0.0, 0.0, 0.0) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bca_interval_falls_back_to_plain_percentile_when_z0_is_nan() {
+        let (alpha1, alpha2) = bca_interval(f64::NAN, 0.1, 0.32);
+        assert_eq!((alpha1, alpha2), (0.16, 0.84));
+    }
+}
@@ -0,0 +1,27 @@
+use roots::{find_root_brent, SearchError, SimpleConvergency};
+
+/// Solve for the effective mass at timeslice `tau` from a folded correlator
+/// `mu`, given the lattice's global time extent, by root-finding the
+/// standard two-exponential cosh ansatz.
+pub fn effective_mass(
+    mu: &[f64],
+    global_t: usize,
+    tau: usize,
+    solver_precision: f64,
+) -> Result<f64, SearchError> {
+    let ratio = mu[tau - 1] / mu[tau];
+    let mut convergency = SimpleConvergency {
+        eps: solver_precision,
+        max_iter: 100,
+    };
+    find_root_brent(
+        1e-12,
+        10.0,
+        |m: f64| {
+            (m * (tau as f64 - global_t as f64 / 2.0)).cosh()
+                / (m * (tau as f64 + 1.0 - global_t as f64 / 2.0)).cosh()
+                - ratio
+        },
+        &mut convergency,
+    )
+}
@@ -0,0 +1,94 @@
+use crate::observables::Measurement;
+use crate::statistics::mean;
+
+/// Which Wilson-flow series a resampling pass should draw from.
+pub enum WilsonFlowObservables {
+    T2Esym,
+}
+
+/// Per-configuration Wilson-flow histories: `t2_esym` is t²⟨E_sym⟩ and `tc`
+/// is the topological charge, both sampled at the flow times in `t`.
+pub struct WilsonFlowData {
+    pub t: Vec<f64>,
+    pub t2_esym: Series,
+    pub tc: Series,
+}
+
+/// A per-configuration time series, one row per configuration.
+pub struct Series {
+    pub nconfs: usize,
+    data: Vec<Vec<f64>>,
+}
+
+impl Series {
+    pub fn new(data: Vec<Vec<f64>>) -> Self {
+        Series {
+            nconfs: data.len(),
+            data,
+        }
+    }
+
+    fn get_subsample_mean_stderr_from_samples(&self, samples: Vec<usize>) -> Measurement {
+        let each_len = self.data[0].len();
+        let mut values = Vec::with_capacity(each_len);
+        for t in 0..each_len {
+            let column: Vec<f64> = samples.iter().map(|&i| self.data[i][t]).collect();
+            values.push(mean(&column));
+        }
+        Measurement { values }
+    }
+}
+
+impl WilsonFlowData {
+    pub fn thermalise(mut self, thermalisation: usize) -> Self {
+        let drop = thermalisation.min(self.t2_esym.data.len());
+        self.t2_esym.data.drain(0..drop);
+        self.tc.data.drain(0..drop);
+        self.t2_esym.nconfs = self.t2_esym.data.len();
+        self.tc.nconfs = self.tc.data.len();
+        self
+    }
+
+    pub fn get_subsample_mean_stderr_from_samples(
+        &self,
+        samples: Vec<usize>,
+        which: WilsonFlowObservables,
+    ) -> Measurement {
+        match which {
+            WilsonFlowObservables::T2Esym => {
+                self.t2_esym.get_subsample_mean_stderr_from_samples(samples)
+            }
+        }
+    }
+}
+
+/// W(t) = t · d/dt [t²⟨E_sym⟩(t)], with the derivative taken by finite
+/// differences (central inside, one-sided at the ends).
+pub fn calculate_w(t2_esym: &[f64], t: &[f64]) -> Vec<f64> {
+    let mut w = Vec::with_capacity(t.len());
+    for i in 0..t.len() {
+        let d = if i == 0 {
+            (t2_esym[1] - t2_esym[0]) / (t[1] - t[0])
+        } else if i == t.len() - 1 {
+            (t2_esym[i] - t2_esym[i - 1]) / (t[i] - t[i - 1])
+        } else {
+            (t2_esym[i + 1] - t2_esym[i - 1]) / (t[i + 1] - t[i - 1])
+        };
+        w.push(t[i] * d);
+    }
+    w
+}
+
+/// Find where W crosses `w_ref`, linearly interpolating between the
+/// bracketing samples. The result is a fractional sample index into `w`, not
+/// converted to a flow time. When no crossing is found, returns the last
+/// value of `w`.
+pub fn calculate_w0(w: Vec<f64>, w_ref: f64) -> f64 {
+    for i in 1..w.len() {
+        if (w[i - 1] - w_ref) * (w[i] - w_ref) <= 0.0 {
+            let frac = (w_ref - w[i - 1]) / (w[i] - w[i - 1]);
+            return (i - 1) as f64 + frac;
+        }
+    }
+    w[w.len() - 1]
+}